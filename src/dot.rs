@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::Graph;
+
+pub struct Config {
+    pub directed: bool,
+    pub node_labels: bool,
+    pub edge_labels: bool,
+    pub record_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            directed: true,
+            node_labels: true,
+            edge_labels: true,
+            record_mode: false,
+        }
+    }
+}
+
+fn escape_label(s: &str, record_mode: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '{' | '}' | '|' | '<' | '>' if record_mode => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+impl<NodeUserData, EdgeUserData> Graph<NodeUserData, EdgeUserData>
+where
+    NodeUserData: fmt::Debug,
+    EdgeUserData: fmt::Debug,
+{
+    pub fn to_dot(&self, config: Config) -> String {
+        let mut out = String::new();
+        let edge_op = if config.directed { "->" } else { "--" };
+        out.push_str(if config.directed { "digraph {\n" } else { "graph {\n" });
+
+        self.visit_nodes(|id, data| {
+            out.push_str(&format!("    N{}", id.raw()));
+            if config.node_labels {
+                out.push_str(&format!(" [label=\"{}\"]", escape_label(&format!("{:?}", data), config.record_mode)));
+            }
+            out.push_str(";\n");
+        });
+
+        self.visit_edges(|id, _, _, data| {
+            let (source, target) = self.edge_endpoints(id);
+            out.push_str(&format!("    N{} {} N{}", source.raw(), edge_op, target.raw()));
+            if config.edge_labels {
+                out.push_str(&format!(" [label=\"{}\"]", escape_label(&format!("{:?}", data), config.record_mode)));
+            }
+            out.push_str(";\n");
+        });
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_label_handles_quotes_backslashes_and_newlines() {
+        assert_eq!(escape_label("a \"quote\"", false), "a \\\"quote\\\"");
+        assert_eq!(escape_label("back\\slash", false), "back\\\\slash");
+        assert_eq!(escape_label("line1\nline2", false), "line1\\nline2");
+    }
+
+    #[test]
+    fn escape_label_only_escapes_record_chars_in_record_mode() {
+        assert_eq!(escape_label("{a|b}<c>", false), "{a|b}<c>");
+        assert_eq!(escape_label("{a|b}<c>", true), "\\{a\\|b\\}\\<c\\>");
+    }
+
+    #[test]
+    fn to_dot_renders_directed_edges_with_node_and_edge_labels() {
+        let mut g: Graph<&str, &str> = Graph::default();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b, "edge");
+
+        let dot = g.to_dot(Config::default());
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains(&format!("N{} -> N{}", a.raw(), b.raw())));
+        assert!(dot.contains("label="));
+    }
+
+    #[test]
+    fn to_dot_can_suppress_labels_and_use_undirected_edges() {
+        let mut g: Graph<&str, &str> = Graph::default();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        g.add_edge(a, b, "edge");
+
+        let dot = g.to_dot(Config {
+            directed: false,
+            node_labels: false,
+            edge_labels: false,
+            record_mode: false,
+        });
+        assert!(dot.starts_with("graph {\n"));
+        assert!(dot.contains(&format!("N{} -- N{}", a.raw(), b.raw())));
+        assert!(!dot.contains("label="));
+    }
+}
@@ -1,24 +1,94 @@
 use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 
+mod dot;
+mod matrix;
+mod shortest_paths;
+mod traversal;
+pub use dot::Config as DotConfig;
+pub use matrix::MatrixError;
+pub use shortest_paths::{astar, astar_with_arity, dijkstra, dijkstra_with_arity, Zero};
+pub use traversal::{Bfs, Dfs};
+
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub struct NodeId(usize);
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub struct EdgeId(usize);
 
+impl EdgeId {
+    fn invalid() -> Self {
+        EdgeId(usize::MAX)
+    }
+    fn is_valid(&self) -> bool {
+        self.0 != usize::MAX
+    }
+}
+
+impl NodeId {
+    pub(crate) fn raw(&self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Direction {
+    Outgoing = 0,
+    Incoming = 1,
+}
+
+const OUTGOING: usize = Direction::Outgoing as usize;
+const INCOMING: usize = Direction::Incoming as usize;
+
 #[derive(Default, Clone)]
 pub struct Edge<EdgeUserData = ()> {
     pub user_data: EdgeUserData,
 }
 
+#[derive(Clone, Copy)]
+struct NodeEntry {
+    first_edge: [EdgeId; 2],
+}
+
+impl Default for NodeEntry {
+    fn default() -> Self {
+        NodeEntry {
+            first_edge: [EdgeId::invalid(), EdgeId::invalid()],
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct EdgeEntry {
+    source: NodeId,
+    target: NodeId,
+    next_edge: [EdgeId; 2],
+}
+
+enum Undo<NodeUserData, EdgeUserData> {
+    NodeData(NodeId, Option<NodeUserData>),
+    NodeEntry(NodeId, Option<NodeEntry>),
+    EdgeData(EdgeId, Option<EdgeUserData>),
+    EdgeEntry(EdgeId, Option<EdgeEntry>),
+}
+
+/// Boundary returned by [`Graph::snapshot`]; pass it to
+/// [`Graph::rollback_to`] or [`Graph::commit`] to close the transaction.
+pub struct Snapshot {
+    log_len: usize,
+}
+
 pub struct Graph<NodeUserData = (), EdgeUserData = ()> {
     next_id: usize,
 
     nodes_data: HashMap<NodeId, NodeUserData>,
     edges_data: HashMap<EdgeId, EdgeUserData>,
 
-    edge_nodes: HashMap<EdgeId, (NodeId, NodeId)>,
+    nodes: HashMap<NodeId, NodeEntry>,
+    edges: HashMap<EdgeId, EdgeEntry>,
+
+    undo_log: Vec<Undo<NodeUserData, EdgeUserData>>,
+    snapshot_depth: usize,
 }
 
 impl<NodeUserData, EdgeUserData> Index<NodeId> for Graph<NodeUserData, EdgeUserData> {
@@ -53,7 +123,10 @@ impl<NodeUserData, EdgeUserData> Default for Graph<NodeUserData, EdgeUserData> {
             next_id: 0,
             nodes_data: HashMap::new(),
             edges_data: HashMap::new(),
-            edge_nodes: HashMap::new(),
+            nodes: HashMap::new(),
+            edges: HashMap::new(),
+            undo_log: Vec::new(),
+            snapshot_depth: 0,
         }
     }
 }
@@ -62,32 +135,162 @@ impl<NodeUserData, EdgeUserData> Graph<NodeUserData, EdgeUserData> {
     pub fn add_node(&mut self, node: NodeUserData) -> NodeId {
         let id = NodeId(self.next_id);
         self.next_id += 1;
+        self.record(Undo::NodeData(id, None));
         self.nodes_data.insert(id, node);
+        self.record(Undo::NodeEntry(id, None));
+        self.nodes.insert(id, NodeEntry::default());
         id
     }
     pub fn remove_node(&mut self, id: NodeId) {
-        let mut edges = Vec::new();
-        for (e, (l, r)) in &self.edge_nodes {
-            if l == &id || r == &id {
-                edges.push(*e);
-            }
-        }
+        let edges: Vec<EdgeId> = self
+            .walk_edges(id, OUTGOING)
+            .chain(self.walk_edges(id, INCOMING))
+            .collect();
         for e in edges {
-            self.edge_nodes.remove(&e);
-            self.edges_data.remove(&e);
+            self.remove_edge(e);
+        }
+        if let Some(entry) = self.nodes.remove(&id) {
+            self.record(Undo::NodeEntry(id, Some(entry)));
+        }
+        if let Some(data) = self.nodes_data.remove(&id) {
+            self.record(Undo::NodeData(id, Some(data)));
         }
-        self.nodes_data.remove(&id);
     }
     pub fn add_edge(&mut self, l: NodeId, r: NodeId, edge: EdgeUserData) -> EdgeId {
         let id = EdgeId(self.next_id);
         self.next_id += 1;
+        self.record(Undo::EdgeData(id, None));
         self.edges_data.insert(id, edge);
-        self.edge_nodes.insert(id, (l, r));
+
+        self.log_node_entry(l);
+        let src_next = self.nodes.get(&l).unwrap().first_edge[OUTGOING];
+        self.nodes.get_mut(&l).unwrap().first_edge[OUTGOING] = id;
+
+        self.log_node_entry(r);
+        let tgt_next = self.nodes.get(&r).unwrap().first_edge[INCOMING];
+        self.nodes.get_mut(&r).unwrap().first_edge[INCOMING] = id;
+
+        self.record(Undo::EdgeEntry(id, None));
+        self.edges.insert(
+            id,
+            EdgeEntry {
+                source: l,
+                target: r,
+                next_edge: [src_next, tgt_next],
+            },
+        );
         id
     }
     pub fn remove_edge(&mut self, id: EdgeId) {
-        self.edge_nodes.remove(&id);
-        self.edges_data.remove(&id);
+        let entry = match self.edges.remove(&id) {
+            Some(entry) => entry,
+            None => return,
+        };
+        self.record(Undo::EdgeEntry(id, Some(entry)));
+        self.unlink(entry.source, OUTGOING, id, entry.next_edge[OUTGOING]);
+        self.unlink(entry.target, INCOMING, id, entry.next_edge[INCOMING]);
+        if let Some(data) = self.edges_data.remove(&id) {
+            self.record(Undo::EdgeData(id, Some(data)));
+        }
+    }
+    fn unlink(&mut self, node: NodeId, dir: usize, id: EdgeId, next_in_dir: EdgeId) {
+        let first = match self.nodes.get(&node) {
+            Some(entry) => entry.first_edge[dir],
+            None => return,
+        };
+        if first == id {
+            self.log_node_entry(node);
+            self.nodes.get_mut(&node).unwrap().first_edge[dir] = next_in_dir;
+            return;
+        }
+        let mut cursor = first;
+        while cursor.is_valid() {
+            let next = self.edges.get(&cursor).unwrap().next_edge[dir];
+            if next == id {
+                self.log_edge_entry(cursor);
+                self.edges.get_mut(&cursor).unwrap().next_edge[dir] = next_in_dir;
+                return;
+            }
+            cursor = next;
+        }
+    }
+    fn record(&mut self, entry: Undo<NodeUserData, EdgeUserData>) {
+        if self.snapshot_depth > 0 {
+            self.undo_log.push(entry);
+        }
+    }
+    fn log_node_entry(&mut self, id: NodeId) {
+        if self.snapshot_depth > 0 {
+            let old = self.nodes.get(&id).copied();
+            self.undo_log.push(Undo::NodeEntry(id, old));
+        }
+    }
+    fn log_edge_entry(&mut self, id: EdgeId) {
+        if self.snapshot_depth > 0 {
+            let old = self.edges.get(&id).copied();
+            self.undo_log.push(Undo::EdgeEntry(id, old));
+        }
+    }
+    fn apply_undo(&mut self, entry: Undo<NodeUserData, EdgeUserData>) {
+        match entry {
+            Undo::NodeData(id, Some(data)) => {
+                self.nodes_data.insert(id, data);
+            }
+            Undo::NodeData(id, None) => {
+                self.nodes_data.remove(&id);
+            }
+            Undo::NodeEntry(id, Some(entry)) => {
+                self.nodes.insert(id, entry);
+            }
+            Undo::NodeEntry(id, None) => {
+                self.nodes.remove(&id);
+            }
+            Undo::EdgeData(id, Some(data)) => {
+                self.edges_data.insert(id, data);
+            }
+            Undo::EdgeData(id, None) => {
+                self.edges_data.remove(&id);
+            }
+            Undo::EdgeEntry(id, Some(entry)) => {
+                self.edges.insert(id, entry);
+            }
+            Undo::EdgeEntry(id, None) => {
+                self.edges.remove(&id);
+            }
+        }
+    }
+    /// Records the current state as an undo boundary. Pair with
+    /// [`Graph::rollback_to`] to undo every `add_node`/`add_edge`/`remove_*`
+    /// performed since, or [`Graph::commit`] to keep them. Snapshots nest
+    /// LIFO: committing an inner snapshot simply folds its log entries into
+    /// the enclosing one, which can still roll them back.
+    pub fn snapshot(&mut self) -> Snapshot {
+        self.snapshot_depth += 1;
+        Snapshot {
+            log_len: self.undo_log.len(),
+        }
+    }
+    pub fn rollback_to(&mut self, snapshot: Snapshot) {
+        while self.undo_log.len() > snapshot.log_len {
+            if let Some(entry) = self.undo_log.pop() {
+                self.apply_undo(entry);
+            }
+        }
+        self.snapshot_depth -= 1;
+    }
+    pub fn commit(&mut self, snapshot: Snapshot) {
+        let _ = snapshot;
+        self.snapshot_depth -= 1;
+        if self.snapshot_depth == 0 {
+            self.undo_log.clear();
+        }
+    }
+    fn walk_edges<'a>(&'a self, node: NodeId, dir: usize) -> EdgeWalk<'a, NodeUserData, EdgeUserData> {
+        EdgeWalk {
+            graph: self,
+            dir,
+            cursor: self.nodes.get(&node).map_or(EdgeId::invalid(), |e| e.first_edge[dir]),
+        }
     }
     pub fn visit_nodes<F: FnMut(NodeId, &NodeUserData)>(&self, mut f: F) {
         for (id, data) in &self.nodes_data {
@@ -99,11 +302,11 @@ impl<NodeUserData, EdgeUserData> Graph<NodeUserData, EdgeUserData> {
             f(*id, data)
         }
     }
-    pub fn visit_edges<F: FnMut(EdgeId, &NodeUserData, &NodeUserData, &EdgeUserData)>(&self, mut f: F) {        
+    pub fn visit_edges<F: FnMut(EdgeId, &NodeUserData, &NodeUserData, &EdgeUserData)>(&self, mut f: F) {
         for (id, data) in &self.edges_data {
-            let (node1, node2) = self.edge_nodes.get(id).unwrap();
-            let data1 = self.nodes_data.get(node1).unwrap();
-            let data2 = self.nodes_data.get(node2).unwrap();
+            let entry = self.edges.get(id).unwrap();
+            let data1 = self.nodes_data.get(&entry.source).unwrap();
+            let data2 = self.nodes_data.get(&entry.target).unwrap();
             f(*id, data1, data2, data)
         }
     }
@@ -119,26 +322,72 @@ impl<NodeUserData, EdgeUserData> Graph<NodeUserData, EdgeUserData> {
     }
     pub fn neighbors_data<'a>(&'a self, id: NodeId) -> NeighborsData<'a, NodeUserData> {
         let mut neighbors = Vec::new();
-        for (_, (l, r)) in &self.edge_nodes {
-            if l == &id {
-                neighbors.push((*l, self.nodes_data.get(l).unwrap()));
-            } else if r == &id {
-                neighbors.push((*r, self.nodes_data.get(r).unwrap()));
-            }            
+        for node in self.walk_edges(id, OUTGOING).map(|e| self.edges.get(&e).unwrap().target)
+            .chain(self.walk_edges(id, INCOMING).map(|e| self.edges.get(&e).unwrap().source))
+        {
+            neighbors.push((node, self.nodes_data.get(&node).unwrap()));
         }
         NeighborsData(neighbors)
     }
-    pub fn neighbors<'a>(&'a self, id: NodeId) -> Neighbors {
+    pub fn neighbors(&self, id: NodeId) -> Neighbors {
         let mut neighbors = Vec::new();
-        for (_, (l, r)) in &self.edge_nodes {
-            if l == &id {
-                neighbors.push(*l);
-            } else if r == &id {
-                neighbors.push(*r);
-            }            
+        for e in self.walk_edges(id, OUTGOING) {
+            neighbors.push(self.edges.get(&e).unwrap().target);
         }
+        for e in self.walk_edges(id, INCOMING) {
+            neighbors.push(self.edges.get(&e).unwrap().source);
+        }
+        Neighbors(neighbors)
+    }
+    pub fn neighbors_directed(&self, id: NodeId, dir: Direction) -> Neighbors {
+        let opposite = match dir {
+            Direction::Outgoing => |e: &EdgeEntry| e.target,
+            Direction::Incoming => |e: &EdgeEntry| e.source,
+        };
+        let neighbors = self
+            .walk_edges(id, dir as usize)
+            .map(|e| opposite(self.edges.get(&e).unwrap()))
+            .collect();
         Neighbors(neighbors)
     }
+    pub fn edges(&self, id: NodeId) -> Edges {
+        let edges = self
+            .walk_edges(id, OUTGOING)
+            .chain(self.walk_edges(id, INCOMING))
+            .collect();
+        Edges(edges)
+    }
+    pub fn edges_directed(&self, id: NodeId, dir: Direction) -> Edges {
+        Edges(self.walk_edges(id, dir as usize).collect())
+    }
+    pub fn out_degree(&self, id: NodeId) -> usize {
+        self.walk_edges(id, OUTGOING).count()
+    }
+    pub fn in_degree(&self, id: NodeId) -> usize {
+        self.walk_edges(id, INCOMING).count()
+    }
+    pub fn edge_endpoints(&self, id: EdgeId) -> (NodeId, NodeId) {
+        let entry = self.edges.get(&id).unwrap();
+        (entry.source, entry.target)
+    }
+}
+
+struct EdgeWalk<'a, NodeUserData, EdgeUserData> {
+    graph: &'a Graph<NodeUserData, EdgeUserData>,
+    dir: usize,
+    cursor: EdgeId,
+}
+
+impl<'a, NodeUserData, EdgeUserData> Iterator for EdgeWalk<'a, NodeUserData, EdgeUserData> {
+    type Item = EdgeId;
+    fn next(&mut self) -> Option<EdgeId> {
+        if !self.cursor.is_valid() {
+            return None;
+        }
+        let id = self.cursor;
+        self.cursor = self.graph.edges.get(&id).unwrap().next_edge[self.dir];
+        Some(id)
+    }
 }
 
 pub struct Neighbors(Vec<NodeId>);
@@ -166,6 +415,31 @@ impl NeighborsIter {
     }
 }
 
+pub struct Edges(Vec<EdgeId>);
+
+impl Edges {
+    pub fn detach(self) -> EdgesIter {
+        EdgesIter(false, 0, self.0)
+    }
+}
+
+pub struct EdgesIter(bool, usize, Vec<EdgeId>);
+
+impl EdgesIter {
+    pub fn next_edge<T>(&mut self, _: T) -> Option<EdgeId> {
+        if self.0 {
+            return None;
+        }
+        let idx = self.1;
+        if idx == self.2.len() {
+            self.0 = true;
+            return None;
+        }
+        self.1 = 1 + idx;
+        Some(self.2[idx])
+    }
+}
+
 pub struct NeighborsData<'a, NodeUserData>(Vec<(NodeId, &'a NodeUserData)>);
 
 impl<'a, NodeUserData> NeighborsData<'a, NodeUserData> {
@@ -207,3 +481,91 @@ impl<'a, 'b, NodeUserData> Iterator for NeighborsDataIter<'a, 'b, NodeUserData>
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors_yields_the_opposite_endpoint() {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, ());
+
+        let mut iter = g.neighbors(a).detach();
+        assert_eq!(iter.next_node(&g), Some(b));
+        assert_eq!(iter.next_node(&g), None);
+
+        let mut iter = g.neighbors(b).detach();
+        assert_eq!(iter.next_node(&g), Some(a));
+        assert_eq!(iter.next_node(&g), None);
+    }
+
+    #[test]
+    fn neighbors_directed_respects_edge_direction() {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, ());
+
+        let mut out = g.neighbors_directed(a, Direction::Outgoing).detach();
+        assert_eq!(out.next_node(&g), Some(b));
+        assert_eq!(out.next_node(&g), None);
+
+        let mut inc = g.neighbors_directed(a, Direction::Incoming).detach();
+        assert_eq!(inc.next_node(&g), None);
+
+        let mut inc = g.neighbors_directed(b, Direction::Incoming).detach();
+        assert_eq!(inc.next_node(&g), Some(a));
+        assert_eq!(inc.next_node(&g), None);
+    }
+
+    #[test]
+    fn remove_node_unlinks_a_self_loop() {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+        let loop_edge = g.add_edge(a, a, ());
+
+        g.remove_node(a);
+
+        assert_eq!(g.node_count(), 0);
+        g.remove_edge(loop_edge);
+    }
+
+    #[test]
+    fn rollback_undoes_an_add_edge() {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+        let b = g.add_node(());
+
+        let snapshot = g.snapshot();
+        g.add_edge(a, b, ());
+        assert_eq!(g.out_degree(a), 1);
+
+        g.rollback_to(snapshot);
+
+        assert_eq!(g.out_degree(a), 0);
+        assert_eq!(g.in_degree(b), 0);
+        let mut iter = g.neighbors(a).detach();
+        assert_eq!(iter.next_node(&g), None);
+    }
+
+    #[test]
+    fn committing_an_inner_snapshot_still_lets_the_outer_one_roll_back() {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+
+        let outer = g.snapshot();
+        let inner = g.snapshot();
+        let b = g.add_node(());
+        g.add_edge(a, b, ());
+        g.commit(inner);
+        assert_eq!(g.node_count(), 2);
+
+        g.rollback_to(outer);
+
+        assert_eq!(g.node_count(), 1);
+        let mut iter = g.neighbors(a).detach();
+        assert_eq!(iter.next_node(&g), None);
+    }
+}
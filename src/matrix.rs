@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{Graph, NodeId};
+
+#[derive(Debug)]
+pub enum MatrixError {
+    RaggedRow { expected: usize, found: usize },
+    InvalidCell(String),
+    InvalidEdgeLine(String),
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::RaggedRow { expected, found } => {
+                write!(f, "row has {} columns, expected {}", found, expected)
+            }
+            MatrixError::InvalidCell(cell) => write!(f, "expected \"0\" or \"1\", found {:?}", cell),
+            MatrixError::InvalidEdgeLine(line) => write!(f, "expected \"src dst\", found {:?}", line),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+impl<NodeUserData, EdgeUserData> Graph<NodeUserData, EdgeUserData> {
+    pub fn from_adjacency_matrix(text: &str) -> Result<Self, MatrixError>
+    where
+        NodeUserData: Default,
+        EdgeUserData: Default,
+    {
+        let rows = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|cell| match cell {
+                        "0" => Ok(0u8),
+                        "1" => Ok(1u8),
+                        other => Err(MatrixError::InvalidCell(other.to_string())),
+                    })
+                    .collect::<Result<Vec<u8>, MatrixError>>()
+            })
+            .collect::<Result<Vec<Vec<u8>>, MatrixError>>()?;
+
+        let node_count = rows.len();
+        for row in &rows {
+            if row.len() != node_count {
+                return Err(MatrixError::RaggedRow {
+                    expected: node_count,
+                    found: row.len(),
+                });
+            }
+        }
+
+        let mut graph = Graph::default();
+        let ids: Vec<NodeId> = (0..node_count).map(|_| graph.add_node(NodeUserData::default())).collect();
+        for (r, row) in rows.iter().enumerate() {
+            for (c, &cell) in row.iter().enumerate() {
+                if cell == 1 {
+                    graph.add_edge(ids[r], ids[c], EdgeUserData::default());
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    pub fn to_adjacency_matrix(&self) -> String {
+        let mut ids = Vec::new();
+        self.visit_nodes(|id, _| ids.push(id));
+        ids.sort_by_key(|id| id.raw());
+        let index: HashMap<NodeId, usize> = ids.iter().enumerate().map(|(i, &id)| (id, i)).collect();
+
+        let mut matrix = vec![vec![0u8; ids.len()]; ids.len()];
+        self.visit_edges(|id, _, _, _| {
+            let (source, target) = self.edge_endpoints(id);
+            matrix[index[&source]][index[&target]] = 1;
+        });
+
+        let mut out = String::new();
+        for row in &matrix {
+            let cells: Vec<String> = row.iter().map(|cell| cell.to_string()).collect();
+            out.push_str(&cells.join(" "));
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn from_edge_list(text: &str) -> Result<Self, MatrixError>
+    where
+        NodeUserData: Default,
+        EdgeUserData: Default,
+    {
+        let mut pairs = Vec::new();
+        let mut max_index = None;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let mut tokens = line.split_whitespace();
+            let parse = |tok: Option<&str>| {
+                tok.and_then(|t| t.parse::<usize>().ok())
+                    .ok_or_else(|| MatrixError::InvalidEdgeLine(line.to_string()))
+            };
+            let src = parse(tokens.next())?;
+            let dst = parse(tokens.next())?;
+            max_index = Some(max_index.map_or(src.max(dst), |m: usize| m.max(src).max(dst)));
+            pairs.push((src, dst));
+        }
+
+        let node_count = max_index.map_or(0, |m| m + 1);
+        let mut graph = Graph::default();
+        let ids: Vec<NodeId> = (0..node_count).map(|_| graph.add_node(NodeUserData::default())).collect();
+        for (src, dst) in pairs {
+            graph.add_edge(ids[src], ids[dst], EdgeUserData::default());
+        }
+        Ok(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_adjacency_matrix_round_trips_through_to_adjacency_matrix() {
+        let text = "0 1 0\n0 0 1\n0 0 0\n";
+        let g: Graph<(), ()> = Graph::from_adjacency_matrix(text).unwrap();
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.to_adjacency_matrix(), text);
+    }
+
+    #[test]
+    fn from_adjacency_matrix_rejects_ragged_rows() {
+        let text = "0 1\n0 0 0\n";
+        let result: Result<Graph<(), ()>, MatrixError> = Graph::from_adjacency_matrix(text);
+        assert!(matches!(result, Err(MatrixError::RaggedRow { expected: 2, found: 3 })));
+    }
+
+    #[test]
+    fn from_adjacency_matrix_rejects_non_bit_cells() {
+        let result: Result<Graph<(), ()>, MatrixError> = Graph::from_adjacency_matrix("0 2\n1 0\n");
+        assert!(matches!(result, Err(MatrixError::InvalidCell(ref cell)) if cell == "2"));
+    }
+
+    #[test]
+    fn from_edge_list_sizes_nodes_to_the_highest_index() {
+        let g: Graph<(), ()> = Graph::from_edge_list("0 2\n2 1\n").unwrap();
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.to_adjacency_matrix(), "0 0 1\n0 0 0\n0 1 0\n");
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::ops::Add;
+
+use crate::{Direction, Graph, NodeId};
+
+pub trait Zero {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_zero {
+    ($($t:ty),*) => {
+        $(impl Zero for $t {
+            fn zero() -> Self { 0 }
+        })*
+    };
+}
+impl_zero!(usize, u8, u16, u32, u64, i8, i16, i32, i64, isize);
+
+struct DHeap<const D: usize, T> {
+    data: Vec<T>,
+}
+
+impl<const D: usize, T: Ord> DHeap<D, T> {
+    fn new() -> Self {
+        DHeap { data: Vec::new() }
+    }
+    fn push(&mut self, item: T) {
+        self.data.push(item);
+        let mut i = self.data.len() - 1;
+        while i > 0 {
+            let parent = (i - 1) / D;
+            if self.data[i] < self.data[parent] {
+                self.data.swap(i, parent);
+                i = parent;
+            } else {
+                break;
+            }
+        }
+    }
+    fn pop(&mut self) -> Option<T> {
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let item = self.data.pop();
+        let len = self.data.len();
+        let mut i = 0;
+        loop {
+            let start = D * i + 1;
+            let mut smallest = i;
+            for c in start..(start + D).min(len) {
+                if self.data[c] < self.data[smallest] {
+                    smallest = c;
+                }
+            }
+            if smallest == i {
+                break;
+            }
+            self.data.swap(i, smallest);
+            i = smallest;
+        }
+        item
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Frontier<C: Ord>(C, NodeId);
+
+pub fn dijkstra<NodeUserData, EdgeUserData, C, F>(
+    graph: &Graph<NodeUserData, EdgeUserData>,
+    start: NodeId,
+    goal: Option<NodeId>,
+    edge_cost: F,
+) -> (HashMap<NodeId, C>, HashMap<NodeId, NodeId>)
+where
+    C: Ord + Add<Output = C> + Zero + Copy,
+    F: Fn(crate::EdgeId, &EdgeUserData) -> C,
+{
+    dijkstra_with_arity::<_, _, _, _, 4>(graph, start, goal, edge_cost)
+}
+
+pub fn dijkstra_with_arity<NodeUserData, EdgeUserData, C, F, const D: usize>(
+    graph: &Graph<NodeUserData, EdgeUserData>,
+    start: NodeId,
+    goal: Option<NodeId>,
+    edge_cost: F,
+) -> (HashMap<NodeId, C>, HashMap<NodeId, NodeId>)
+where
+    C: Ord + Add<Output = C> + Zero + Copy,
+    F: Fn(crate::EdgeId, &EdgeUserData) -> C,
+{
+    let mut best_cost = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut frontier: DHeap<D, Frontier<C>> = DHeap::new();
+
+    best_cost.insert(start, C::zero());
+    frontier.push(Frontier(C::zero(), start));
+
+    while let Some(Frontier(cost, node)) = frontier.pop() {
+        if Some(node) == goal {
+            break;
+        }
+        if best_cost.get(&node).is_none_or(|&best| cost > best) {
+            continue;
+        }
+        let mut iter = graph.edges_directed(node, Direction::Outgoing).detach();
+        while let Some(edge) = iter.next_edge(graph) {
+            let (_, target) = graph.edge_endpoints(edge);
+            let next_cost = cost + edge_cost(edge, &graph[edge]);
+            if best_cost.get(&target).is_none_or(|&best| next_cost < best) {
+                best_cost.insert(target, next_cost);
+                predecessor.insert(target, node);
+                frontier.push(Frontier(next_cost, target));
+            }
+        }
+    }
+    (best_cost, predecessor)
+}
+
+#[cfg(test)]
+fn weighted_diamond() -> (Graph<(), u32>, NodeId, NodeId, NodeId) {
+    let mut g: Graph<(), u32> = Graph::default();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    g.add_edge(a, b, 1);
+    g.add_edge(a, c, 4);
+    g.add_edge(b, c, 1);
+    (g, a, b, c)
+}
+
+#[cfg(test)]
+fn star_graph(leaves: usize) -> (Graph<(), u32>, NodeId, Vec<NodeId>) {
+    let mut g: Graph<(), u32> = Graph::default();
+    let root = g.add_node(());
+    let mut ids = Vec::with_capacity(leaves);
+    for i in 0..leaves {
+        let leaf = g.add_node(());
+        g.add_edge(root, leaf, (leaves - i) as u32);
+        ids.push(leaf);
+    }
+    (g, root, ids)
+}
+
+pub fn astar<NodeUserData, EdgeUserData, C, F, H>(
+    graph: &Graph<NodeUserData, EdgeUserData>,
+    start: NodeId,
+    goal: NodeId,
+    edge_cost: F,
+    heuristic: H,
+) -> (HashMap<NodeId, C>, HashMap<NodeId, NodeId>)
+where
+    C: Ord + Add<Output = C> + Zero + Copy,
+    F: Fn(crate::EdgeId, &EdgeUserData) -> C,
+    H: Fn(NodeId) -> C,
+{
+    astar_with_arity::<_, _, _, _, _, 4>(graph, start, goal, edge_cost, heuristic)
+}
+
+pub fn astar_with_arity<NodeUserData, EdgeUserData, C, F, H, const D: usize>(
+    graph: &Graph<NodeUserData, EdgeUserData>,
+    start: NodeId,
+    goal: NodeId,
+    edge_cost: F,
+    heuristic: H,
+) -> (HashMap<NodeId, C>, HashMap<NodeId, NodeId>)
+where
+    C: Ord + Add<Output = C> + Zero + Copy,
+    F: Fn(crate::EdgeId, &EdgeUserData) -> C,
+    H: Fn(NodeId) -> C,
+{
+    let mut best_cost = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut frontier: DHeap<D, Frontier<C>> = DHeap::new();
+
+    best_cost.insert(start, C::zero());
+    frontier.push(Frontier(heuristic(start), start));
+
+    while let Some(Frontier(priority, node)) = frontier.pop() {
+        if node == goal {
+            break;
+        }
+        let node_cost = match best_cost.get(&node) {
+            Some(&cost) => cost,
+            None => continue,
+        };
+        if priority > node_cost + heuristic(node) {
+            continue;
+        }
+        let mut iter = graph.edges_directed(node, Direction::Outgoing).detach();
+        while let Some(edge) = iter.next_edge(graph) {
+            let (_, target) = graph.edge_endpoints(edge);
+            let next_cost = node_cost + edge_cost(edge, &graph[edge]);
+            if best_cost.get(&target).is_none_or(|&best| next_cost < best) {
+                best_cost.insert(target, next_cost);
+                predecessor.insert(target, node);
+                frontier.push(Frontier(next_cost + heuristic(target), target));
+            }
+        }
+    }
+    (best_cost, predecessor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dijkstra_prefers_the_cheaper_detour() {
+        let (g, a, b, c) = weighted_diamond();
+        let (cost, predecessor) = dijkstra(&g, a, None, |_, &weight| weight);
+        assert_eq!(cost[&c], 2);
+        assert_eq!(predecessor[&c], b);
+    }
+
+    #[test]
+    fn astar_with_zero_heuristic_matches_dijkstra() {
+        let (g, a, b, c) = weighted_diamond();
+        let (cost, predecessor) = astar(&g, a, c, |_, &weight| weight, |_| 0);
+        assert_eq!(cost[&c], 2);
+        assert_eq!(predecessor[&c], b);
+    }
+
+    #[test]
+    fn dheap_pops_in_ascending_order_across_multiple_levels() {
+        let mut heap: DHeap<2, i32> = DHeap::new();
+        let values = [17, 3, 9, 42, 1, 8, 23, 4, 16, 15, 0, 11, 19, 2, 6];
+        for &v in &values {
+            heap.push(v);
+        }
+
+        let mut popped = Vec::new();
+        while let Some(v) = heap.pop() {
+            popped.push(v);
+        }
+
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(popped, expected);
+    }
+
+    #[test]
+    fn dijkstra_with_arity_handles_a_wide_frontier_at_non_default_arity() {
+        let (g, root, leaves) = star_graph(12);
+        let (cost, _predecessor) = dijkstra_with_arity::<_, _, _, _, 2>(&g, root, None, |_, &weight| weight);
+        for (i, &leaf) in leaves.iter().enumerate() {
+            assert_eq!(cost[&leaf], (leaves.len() - i) as u32);
+        }
+    }
+
+    #[test]
+    fn astar_with_arity_handles_a_wide_frontier_at_non_default_arity() {
+        let (g, root, leaves) = star_graph(12);
+        let cheapest_leaf = leaves[leaves.len() - 1];
+        let (cost, _predecessor) =
+            astar_with_arity::<_, _, _, _, _, 8>(&g, root, cheapest_leaf, |_, &weight| weight, |_| 0);
+        assert_eq!(cost[&cheapest_leaf], 1);
+    }
+}
@@ -0,0 +1,98 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{Direction, Graph, NodeId};
+
+pub struct Bfs {
+    queue: VecDeque<NodeId>,
+    visited: HashSet<NodeId>,
+}
+
+impl Bfs {
+    pub fn new(start: NodeId) -> Self {
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        Bfs { queue, visited }
+    }
+    pub fn next<NodeUserData, EdgeUserData>(
+        &mut self,
+        graph: &Graph<NodeUserData, EdgeUserData>,
+    ) -> Option<NodeId> {
+        let node = self.queue.pop_front()?;
+        let mut iter = graph.neighbors_directed(node, Direction::Outgoing).detach();
+        while let Some(neighbor) = iter.next_node(graph) {
+            if self.visited.insert(neighbor) {
+                self.queue.push_back(neighbor);
+            }
+        }
+        Some(node)
+    }
+}
+
+pub struct Dfs {
+    stack: Vec<NodeId>,
+    visited: HashSet<NodeId>,
+}
+
+impl Dfs {
+    pub fn new(start: NodeId) -> Self {
+        let mut visited = HashSet::new();
+        visited.insert(start);
+        Dfs {
+            stack: vec![start],
+            visited,
+        }
+    }
+    pub fn next<NodeUserData, EdgeUserData>(
+        &mut self,
+        graph: &Graph<NodeUserData, EdgeUserData>,
+    ) -> Option<NodeId> {
+        let node = self.stack.pop()?;
+        let mut iter = graph.neighbors_directed(node, Direction::Outgoing).detach();
+        while let Some(neighbor) = iter.next_node(graph) {
+            if self.visited.insert(neighbor) {
+                self.stack.push(neighbor);
+            }
+        }
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn line_graph() -> (Graph<(), ()>, NodeId, NodeId, NodeId) {
+        let mut g: Graph<(), ()> = Graph::default();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, ());
+        g.add_edge(b, c, ());
+        (g, a, b, c)
+    }
+
+    #[test]
+    fn bfs_visits_every_reachable_node_once() {
+        let (g, a, b, c) = line_graph();
+        let mut bfs = Bfs::new(a);
+        let mut seen = HashSet::new();
+        while let Some(node) = bfs.next(&g) {
+            assert!(seen.insert(node), "node visited twice: {:?}", node);
+        }
+        assert_eq!(seen, HashSet::from([a, b, c]));
+    }
+
+    #[test]
+    fn dfs_visits_every_reachable_node_once() {
+        let (g, a, b, c) = line_graph();
+        let mut dfs = Dfs::new(a);
+        let mut seen = HashSet::new();
+        while let Some(node) = dfs.next(&g) {
+            assert!(seen.insert(node), "node visited twice: {:?}", node);
+        }
+        assert_eq!(seen, HashSet::from([a, b, c]));
+    }
+}